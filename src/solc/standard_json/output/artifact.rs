@@ -0,0 +1,364 @@
+//!
+//! The artifact writer abstraction.
+//!
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use super::contract::Contract;
+use super::Output;
+
+///
+/// A single selectable piece of a contract artifact.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OutputSelector {
+    /// The contract ABI.
+    Abi,
+    /// The EVM data, including bytecode.
+    Bytecode,
+    /// The optimized Yul IR.
+    IrOptimized,
+    /// The EraVM assembly.
+    Assembly,
+    /// The contract metadata.
+    Metadata,
+    /// The abstract syntax tree.
+    Ast,
+}
+
+impl OutputSelector {
+    ///
+    /// The top-level serialized contract key this selector retains.
+    ///
+    fn json_key(&self) -> &'static str {
+        match self {
+            Self::Abi => "abi",
+            Self::Bytecode => "evm",
+            Self::IrOptimized => "irOptimized",
+            Self::Assembly => "evm",
+            Self::Metadata => "metadata",
+            Self::Ast => "ast",
+        }
+    }
+}
+
+///
+/// The set of contract pieces a caller wants written to disk.
+///
+pub type OutputSelection = BTreeSet<OutputSelector>;
+
+///
+/// A writer that turns an [`Output`] into per-contract artifact files,
+/// honoring an [`OutputSelection`] and skipping contracts with no bytecode.
+///
+pub trait ArtifactOutput {
+    ///
+    /// Writes a single contract's selected pieces under `directory`.
+    ///
+    /// `ast` is the contract's file-level AST from `Output.sources`, merged in
+    /// under the `ast` key when [`OutputSelector::Ast`] is selected; `Contract`
+    /// itself carries no AST.
+    ///
+    fn write_contract(
+        &self,
+        full_path: &str,
+        contract: &Contract,
+        ast: Option<&serde_json::Value>,
+        selection: &OutputSelection,
+        directory: &Path,
+    ) -> anyhow::Result<()>;
+
+    ///
+    /// Writes every non-empty contract in `output` under `directory`, pairing
+    /// each contract with its file's AST from `output.sources`.
+    ///
+    fn write(
+        &self,
+        output: &Output,
+        selection: &OutputSelection,
+        directory: &Path,
+    ) -> anyhow::Result<()> {
+        let files = match output.contracts.as_ref() {
+            Some(files) => files,
+            None => return Ok(()),
+        };
+
+        for (path, contracts) in files.iter() {
+            let ast = output
+                .sources
+                .as_ref()
+                .and_then(|sources| sources.get(path))
+                .and_then(|source| source.ast.as_ref())
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(|error| anyhow::anyhow!("Source `{}` AST serialization error: {}", path, error))?;
+
+            for (name, contract) in contracts.iter() {
+                if is_bytecode_empty(contract) {
+                    continue;
+                }
+                let full_path = format!("{path}:{name}");
+                self.write_contract(full_path.as_str(), contract, ast.as_ref(), selection, directory)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// The default artifact writer: one pretty-printed JSON file per `path:name`
+/// contract, containing only the selected pieces.
+///
+#[derive(Debug, Default, Clone)]
+pub struct JsonArtifactOutput;
+
+impl ArtifactOutput for JsonArtifactOutput {
+    fn write_contract(
+        &self,
+        full_path: &str,
+        contract: &Contract,
+        ast: Option<&serde_json::Value>,
+        selection: &OutputSelection,
+        directory: &Path,
+    ) -> anyhow::Result<()> {
+        let value = serde_json::to_value(contract).map_err(|error| {
+            anyhow::anyhow!("Contract `{}` serialization error: {}", full_path, error)
+        })?;
+        let mut value = prune(value, selection);
+        if selection.contains(&OutputSelector::Ast) {
+            if let (Some(object), Some(ast)) = (value.as_object_mut(), ast) {
+                object.insert("ast".to_owned(), ast.to_owned());
+            }
+        }
+
+        let file_name = format!("{}.json", full_path.replace([':', '/'], "_"));
+        let file_path = directory.join(file_name);
+        let text = serde_json::to_string_pretty(&value).expect("Always valid");
+        std::fs::write(file_path.as_path(), text).map_err(|error| {
+            anyhow::anyhow!("Artifact file {:?} writing error: {}", file_path, error)
+        })
+    }
+}
+
+///
+/// A no-op artifact sink, for callers that only need in-memory results.
+///
+#[derive(Debug, Default, Clone)]
+pub struct NoopArtifactOutput;
+
+impl ArtifactOutput for NoopArtifactOutput {
+    fn write_contract(
+        &self,
+        _full_path: &str,
+        _contract: &Contract,
+        _ast: Option<&serde_json::Value>,
+        _selection: &OutputSelection,
+        _directory: &Path,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+///
+/// Reloads a previously written artifact file back into a [`Contract`].
+///
+pub fn read_contract(path: &Path) -> anyhow::Result<Contract> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| anyhow::anyhow!("Artifact file {:?} reading error: {}", path, error))?;
+    serde_json::from_str(text.as_str())
+        .map_err(|error| anyhow::anyhow!("Artifact file {:?} parsing error: {}", path, error))
+}
+
+///
+/// Retains only the selected pieces of a serialized contract, narrowing the
+/// `evm` object to the bytecode and/or assembly sub-keys actually requested.
+///
+fn prune(mut value: serde_json::Value, selection: &OutputSelection) -> serde_json::Value {
+    let object = match value.as_object_mut() {
+        Some(object) => object,
+        None => return value,
+    };
+
+    let allowed: BTreeSet<&'static str> = selection.iter().map(OutputSelector::json_key).collect();
+    object.retain(|key, _| allowed.contains(key.as_str()));
+
+    if let Some(evm) = object.get_mut("evm").and_then(serde_json::Value::as_object_mut) {
+        let mut evm_allowed: BTreeSet<&'static str> = BTreeSet::new();
+        if selection.contains(&OutputSelector::Bytecode) {
+            evm_allowed.extend(["bytecode", "deployedBytecode", "methodIdentifiers"]);
+        }
+        if selection.contains(&OutputSelector::Assembly) {
+            evm_allowed.extend(["assembly", "legacyAssembly"]);
+        }
+        evm.retain(|key, _| evm_allowed.contains(key.as_str()));
+    }
+
+    value
+}
+
+///
+/// Returns whether the contract carries no deploy bytecode and should be
+/// skipped when writing artifacts.
+///
+fn is_bytecode_empty(contract: &Contract) -> bool {
+    contract
+        .evm
+        .as_ref()
+        .and_then(|evm| evm.bytecode.as_ref())
+        .map(|bytecode| bytecode.object.is_empty())
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prune;
+    use super::read_contract;
+    use super::ArtifactOutput;
+    use super::Contract;
+    use super::JsonArtifactOutput;
+    use super::Output;
+    use super::OutputSelection;
+    use super::OutputSelector;
+
+    fn contract_value() -> serde_json::Value {
+        serde_json::json!({
+            "abi": [],
+            "metadata": "{}",
+            "irOptimized": "object \"A\" {}",
+            "evm": {
+                "bytecode": { "object": "0x00" },
+                "legacyAssembly": { "code": [] },
+                "methodIdentifiers": {}
+            }
+        })
+    }
+
+    #[test]
+    fn prune_keeps_only_selected_top_level_keys() {
+        let selection: OutputSelection = [OutputSelector::Abi].into_iter().collect();
+        let pruned = prune(contract_value(), &selection);
+        let object = pruned.as_object().expect("object");
+        assert!(object.contains_key("abi"));
+        assert!(!object.contains_key("metadata"));
+        assert!(!object.contains_key("evm"));
+    }
+
+    #[test]
+    fn prune_bytecode_excludes_assembly() {
+        let selection: OutputSelection = [OutputSelector::Bytecode].into_iter().collect();
+        let pruned = prune(contract_value(), &selection);
+        let evm = pruned["evm"].as_object().expect("evm object");
+        assert!(evm.contains_key("bytecode"));
+        assert!(!evm.contains_key("legacyAssembly"));
+    }
+
+    #[test]
+    fn prune_assembly_excludes_bytecode() {
+        let selection: OutputSelection = [OutputSelector::Assembly].into_iter().collect();
+        let pruned = prune(contract_value(), &selection);
+        let evm = pruned["evm"].as_object().expect("evm object");
+        assert!(evm.contains_key("legacyAssembly"));
+        assert!(!evm.contains_key("bytecode"));
+    }
+
+    #[test]
+    fn written_artifact_round_trips_through_json() {
+        let contract: Contract = serde_json::from_value(contract_value()).expect("contract");
+        let selection: OutputSelection =
+            [OutputSelector::Abi, OutputSelector::Bytecode].into_iter().collect();
+
+        let directory = std::env::temp_dir().join("zksolc_artifact_round_trip_test");
+        std::fs::create_dir_all(directory.as_path()).expect("create dir");
+
+        JsonArtifactOutput
+            .write_contract("A.sol:A", &contract, None, &selection, directory.as_path())
+            .expect("write contract");
+
+        let file_path = directory.join("A.sol_A.json");
+        let reloaded = read_contract(file_path.as_path()).expect("read contract");
+
+        assert_eq!(reloaded.abi, contract.abi);
+        assert!(reloaded
+            .evm
+            .as_ref()
+            .and_then(|evm| evm.bytecode.as_ref())
+            .is_some());
+        assert!(reloaded.metadata.is_none());
+
+        let _ = std::fs::remove_file(file_path.as_path());
+        let _ = std::fs::remove_dir(directory.as_path());
+    }
+
+    #[test]
+    fn write_contract_merges_ast_only_when_selected() {
+        let contract: Contract = serde_json::from_value(contract_value()).expect("contract");
+        let ast = serde_json::json!({ "nodes": [] });
+
+        let directory = std::env::temp_dir().join("zksolc_artifact_ast_selection_test");
+        std::fs::create_dir_all(directory.as_path()).expect("create dir");
+
+        let selected: OutputSelection = [OutputSelector::Abi, OutputSelector::Ast].into_iter().collect();
+        JsonArtifactOutput
+            .write_contract("A.sol:A", &contract, Some(&ast), &selected, directory.as_path())
+            .expect("write contract");
+        let with_ast: serde_json::Value = serde_json::from_str(
+            std::fs::read_to_string(directory.join("A.sol_A.json")).expect("read file").as_str(),
+        )
+        .expect("parse");
+        assert_eq!(with_ast["ast"], ast);
+
+        let unselected: OutputSelection = [OutputSelector::Abi].into_iter().collect();
+        JsonArtifactOutput
+            .write_contract("A.sol:A", &contract, Some(&ast), &unselected, directory.as_path())
+            .expect("write contract");
+        let without_ast: serde_json::Value = serde_json::from_str(
+            std::fs::read_to_string(directory.join("A.sol_A.json")).expect("read file").as_str(),
+        )
+        .expect("parse");
+        assert!(without_ast.get("ast").is_none());
+
+        let _ = std::fs::remove_file(directory.join("A.sol_A.json"));
+        let _ = std::fs::remove_dir(directory.as_path());
+    }
+
+    fn output_with_contract_and_ast() -> Output {
+        serde_json::from_value(serde_json::json!({
+            "contracts": {
+                "A.sol": {
+                    "A": {
+                        "abi": [],
+                        "evm": { "bytecode": { "object": "0x00" } },
+                    }
+                }
+            },
+            "sources": {
+                "A.sol": { "ast": { "nodes": [] } },
+            },
+        }))
+        .expect("output")
+    }
+
+    #[test]
+    fn write_pairs_each_contract_with_its_file_ast() {
+        let output = output_with_contract_and_ast();
+        let selection: OutputSelection = [OutputSelector::Abi, OutputSelector::Ast].into_iter().collect();
+
+        let directory = std::env::temp_dir().join("zksolc_artifact_write_ast_test");
+        std::fs::create_dir_all(directory.as_path()).expect("create dir");
+
+        JsonArtifactOutput
+            .write(&output, &selection, directory.as_path())
+            .expect("write");
+
+        let file_path = directory.join("A.sol_A.json");
+        let written: serde_json::Value =
+            serde_json::from_str(std::fs::read_to_string(file_path.as_path()).expect("read file").as_str())
+                .expect("parse");
+        assert_eq!(written["ast"], serde_json::json!({ "nodes": [] }));
+
+        let _ = std::fs::remove_file(file_path.as_path());
+        let _ = std::fs::remove_dir(directory.as_path());
+    }
+}
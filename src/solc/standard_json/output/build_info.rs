@@ -0,0 +1,129 @@
+//!
+//! The reproducible build-info artifact.
+//!
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::solc::standard_json::input::Input as StandardJsonInput;
+
+use super::cache::keccak256;
+use super::Output;
+
+///
+/// A self-contained, reproducible build-info artifact.
+///
+/// It bundles the standard-JSON compiler input that produced an [`Output`]
+/// together with the output itself and the compiler version triple. Given the
+/// artifact alone, a downstream consumer can re-run the exact same compilation
+/// and diff the resulting contracts and sources.
+///
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildInfo {
+    /// The stable build identifier derived from the input and compiler version.
+    pub id: String,
+    /// The `solc` compiler version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The `solc` compiler long version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long_version: Option<String>,
+    /// The `zksolc` compiler version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zk_version: Option<String>,
+    /// The standard-JSON compiler input.
+    pub input: StandardJsonInput,
+    /// The standard-JSON compiler output.
+    pub output: Output,
+}
+
+impl BuildInfo {
+    ///
+    /// Bundles `input` and `output` into a build-info artifact with a
+    /// deterministic build id.
+    ///
+    pub fn new(input: StandardJsonInput, output: Output) -> anyhow::Result<Self> {
+        let id = Self::build_id(
+            &input,
+            output.version.as_deref(),
+            output.long_version.as_deref(),
+            output.zk_version.as_deref(),
+        )?;
+
+        Ok(Self {
+            id,
+            version: output.version.clone(),
+            long_version: output.long_version.clone(),
+            zk_version: output.zk_version.clone(),
+            input,
+            output,
+        })
+    }
+
+    ///
+    /// Derives the build id as the keccak256 of the canonicalized input JSON
+    /// concatenated with the compiler version triple.
+    ///
+    fn build_id(
+        input: &StandardJsonInput,
+        version: Option<&str>,
+        long_version: Option<&str>,
+        zk_version: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let mut preimage = serde_json::to_vec(input)
+            .map_err(|error| anyhow::anyhow!("Build-info input serialization error: {}", error))?;
+        preimage.extend_from_slice(version.unwrap_or_default().as_bytes());
+        preimage.extend_from_slice(long_version.unwrap_or_default().as_bytes());
+        preimage.extend_from_slice(zk_version.unwrap_or_default().as_bytes());
+        Ok(keccak256(preimage.as_slice()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BuildInfo;
+    use crate::solc::standard_json::input::Input as StandardJsonInput;
+
+    fn input() -> StandardJsonInput {
+        serde_json::from_value(serde_json::json!({
+            "language": "Yul",
+            "sources": {},
+            "settings": {},
+        }))
+        .expect("standard-json input")
+    }
+
+    #[test]
+    fn build_id_is_deterministic_and_version_sensitive() {
+        let id = BuildInfo::build_id(&input(), Some("0.8.0"), Some("0.8.0+commit.c7dfd78e"), Some("1.5.0"))
+            .expect("build id");
+        let same = BuildInfo::build_id(&input(), Some("0.8.0"), Some("0.8.0+commit.c7dfd78e"), Some("1.5.0"))
+            .expect("build id");
+        assert_eq!(id, same);
+
+        let different_version =
+            BuildInfo::build_id(&input(), Some("0.8.1"), Some("0.8.0+commit.c7dfd78e"), Some("1.5.0"))
+                .expect("build id");
+        assert_ne!(id, different_version);
+    }
+
+    #[test]
+    fn new_embeds_the_same_id_as_build_id() {
+        let input = input();
+        let expected_id =
+            BuildInfo::build_id(&input, Some("0.8.0"), Some("0.8.0+commit.c7dfd78e"), Some("1.5.0"))
+                .expect("build id");
+
+        let output = super::Output {
+            contracts: None,
+            sources: None,
+            errors: None,
+            version: Some("0.8.0".to_owned()),
+            long_version: Some("0.8.0+commit.c7dfd78e".to_owned()),
+            zk_version: Some("1.5.0".to_owned()),
+        };
+
+        let build_info = BuildInfo::new(input, output).expect("build info");
+        assert_eq!(build_info.id, expected_id);
+    }
+}
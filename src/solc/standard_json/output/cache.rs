@@ -0,0 +1,302 @@
+//!
+//! The incremental lowering cache for `Output::try_to_project`.
+//!
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Serialize;
+use sha3::Digest;
+use sha3::Keccak256;
+
+use crate::project::contract::Contract as ProjectContract;
+use crate::solc::pipeline::Pipeline as SolcPipeline;
+
+///
+/// A single cached contract lowering, keyed by `full_path` in the parent map.
+///
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Entry {
+    /// The keccak256 of the contract's Yul `ir_optimized` or EVM assembly.
+    pub source_hash: String,
+    /// The serialized `ProjectContract`, reused verbatim on a cache hit.
+    pub contract: serde_json::Value,
+}
+
+///
+/// The on-disk incremental cache that lets repeated compilations skip
+/// re-lexing and re-parsing contracts whose `ir_optimized`/assembly hash is
+/// unchanged.
+///
+/// The key is the source hash only, not the full `solc` output, so a stale
+/// ABI/metadata for byte-identical IR is served from cache. All entries are
+/// dropped on load when the `solc` or `zksolc` version, the pipeline or the
+/// library map changes (see `load_invalidates_on_version_change`); disabling
+/// caching also forces a refresh.
+///
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Cache {
+    /// The `solc` compiler long version.
+    pub solc_long_version: String,
+    /// The `zksolc` compiler version.
+    pub zk_version: String,
+    /// The selected `solc` codegen pipeline.
+    pub pipeline: SolcPipeline,
+    /// The keccak256 of the serialized library map.
+    pub libraries_hash: String,
+    /// The per-contract cached lowerings.
+    pub entries: BTreeMap<String, Entry>,
+}
+
+impl Cache {
+    ///
+    /// Creates an empty cache for the given compilation inputs.
+    ///
+    pub fn new(
+        solc_long_version: String,
+        zk_version: String,
+        pipeline: SolcPipeline,
+        libraries: &BTreeMap<String, BTreeMap<String, String>>,
+    ) -> Self {
+        Self {
+            solc_long_version,
+            zk_version,
+            pipeline,
+            libraries_hash: Self::hash_libraries(libraries),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// Loads the cache from `path`, discarding all entries if the compiler
+    /// versions, pipeline or library map no longer match the current inputs.
+    ///
+    pub fn load(
+        path: &Path,
+        solc_long_version: String,
+        zk_version: String,
+        pipeline: SolcPipeline,
+        libraries: &BTreeMap<String, BTreeMap<String, String>>,
+    ) -> anyhow::Result<Self> {
+        let fresh = Self::new(solc_long_version, zk_version, pipeline, libraries);
+
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(fresh),
+            Err(error) => {
+                anyhow::bail!("Cache file {:?} reading error: {}", path, error);
+            }
+        };
+        let loaded: Self = serde_json::from_str(text.as_str())
+            .map_err(|error| anyhow::anyhow!("Cache file {:?} parsing error: {}", path, error))?;
+
+        if loaded.solc_long_version != fresh.solc_long_version
+            || loaded.zk_version != fresh.zk_version
+            || loaded.pipeline != fresh.pipeline
+            || loaded.libraries_hash != fresh.libraries_hash
+        {
+            return Ok(fresh);
+        }
+
+        Ok(loaded)
+    }
+
+    ///
+    /// Returns the cached serialized contract for `full_path` if its stored
+    /// hash matches `source_hash`.
+    ///
+    pub fn get(&self, full_path: &str, source_hash: &str) -> Option<&serde_json::Value> {
+        self.entries
+            .get(full_path)
+            .filter(|entry| entry.source_hash == source_hash)
+            .map(|entry| &entry.contract)
+    }
+
+    ///
+    /// Records a freshly lowered contract, overwriting any stale entry.
+    ///
+    pub fn insert(
+        &mut self,
+        full_path: String,
+        source_hash: String,
+        contract: &ProjectContract,
+    ) -> anyhow::Result<()> {
+        let contract = serde_json::to_value(contract)
+            .map_err(|error| anyhow::anyhow!("Cache entry serialization error: {}", error))?;
+        self.entries.insert(
+            full_path,
+            Entry {
+                source_hash,
+                contract,
+            },
+        );
+        Ok(())
+    }
+
+    ///
+    /// Discards entries for contracts not present in `visited`, so renamed or
+    /// deleted contracts do not accumulate in the cache forever.
+    ///
+    pub fn retain_visited(&mut self, visited: &BTreeSet<String>) {
+        self.entries.retain(|full_path, _| visited.contains(full_path));
+    }
+
+    ///
+    /// Writes the merged cache back to `path`.
+    ///
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        let text = serde_json::to_string(self).expect("Always valid");
+        std::fs::write(path, text)
+            .map_err(|error| anyhow::anyhow!("Cache file {:?} writing error: {}", path, error))
+    }
+
+    ///
+    /// Computes the keccak256 of the canonicalized library map.
+    ///
+    fn hash_libraries(libraries: &BTreeMap<String, BTreeMap<String, String>>) -> String {
+        let serialized = serde_json::to_vec(libraries).expect("Always valid");
+        keccak256(serialized.as_slice())
+    }
+}
+
+///
+/// Computes the keccak256 of `bytes` as a `0x`-prefixed hex string.
+///
+pub fn keccak256(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(Keccak256::digest(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::collections::BTreeSet;
+
+    use crate::solc::pipeline::Pipeline as SolcPipeline;
+
+    use super::keccak256;
+    use super::Cache;
+    use super::Entry;
+
+    fn libraries() -> BTreeMap<String, BTreeMap<String, String>> {
+        BTreeMap::new()
+    }
+
+    #[test]
+    fn keccak256_is_deterministic() {
+        assert_eq!(keccak256(b"object"), keccak256(b"object"));
+        assert_ne!(keccak256(b"object"), keccak256(b"other"));
+    }
+
+    #[test]
+    fn get_matches_only_on_equal_hash() {
+        let mut cache =
+            Cache::new("v".to_owned(), "zk".to_owned(), SolcPipeline::Yul, &libraries());
+        cache.entries.insert(
+            "A.sol:A".to_owned(),
+            Entry {
+                source_hash: "0x01".to_owned(),
+                contract: serde_json::json!({ "a": 1 }),
+            },
+        );
+
+        assert!(cache.get("A.sol:A", "0x01").is_some());
+        assert!(cache.get("A.sol:A", "0x02").is_none());
+        assert!(cache.get("B.sol:B", "0x01").is_none());
+    }
+
+    #[test]
+    fn load_reuses_on_match_and_invalidates_on_library_change() {
+        let path = std::env::temp_dir().join("zksolc_cache_test_invalidation.json");
+        let mut cache =
+            Cache::new("v".to_owned(), "zk".to_owned(), SolcPipeline::Yul, &libraries());
+        cache.entries.insert(
+            "A.sol:A".to_owned(),
+            Entry {
+                source_hash: "0x01".to_owned(),
+                contract: serde_json::json!({}),
+            },
+        );
+        cache.write(path.as_path()).expect("cache write");
+
+        let reused = Cache::load(
+            path.as_path(),
+            "v".to_owned(),
+            "zk".to_owned(),
+            SolcPipeline::Yul,
+            &libraries(),
+        )
+        .expect("cache load");
+        assert!(reused.get("A.sol:A", "0x01").is_some());
+
+        let mut changed = libraries();
+        changed.insert(
+            "L.sol".to_owned(),
+            BTreeMap::from([("L".to_owned(), "0x00".to_owned())]),
+        );
+        let invalidated = Cache::load(
+            path.as_path(),
+            "v".to_owned(),
+            "zk".to_owned(),
+            SolcPipeline::Yul,
+            &changed,
+        )
+        .expect("cache load");
+        assert!(invalidated.entries.is_empty());
+
+        let _ = std::fs::remove_file(path.as_path());
+    }
+
+    #[test]
+    fn load_invalidates_on_version_change() {
+        let path = std::env::temp_dir().join("zksolc_cache_test_version_invalidation.json");
+        let mut cache = Cache::new("v1".to_owned(), "zk".to_owned(), SolcPipeline::Yul, &libraries());
+        cache.entries.insert(
+            "A.sol:A".to_owned(),
+            Entry {
+                source_hash: "0x01".to_owned(),
+                contract: serde_json::json!({}),
+            },
+        );
+        cache.write(path.as_path()).expect("cache write");
+
+        let invalidated = Cache::load(
+            path.as_path(),
+            "v2".to_owned(),
+            "zk".to_owned(),
+            SolcPipeline::Yul,
+            &libraries(),
+        )
+        .expect("cache load");
+        assert!(invalidated.entries.is_empty());
+
+        let _ = std::fs::remove_file(path.as_path());
+    }
+
+    #[test]
+    fn retain_visited_drops_entries_for_renamed_or_deleted_contracts() {
+        let mut cache =
+            Cache::new("v".to_owned(), "zk".to_owned(), SolcPipeline::Yul, &libraries());
+        cache.entries.insert(
+            "A.sol:A".to_owned(),
+            Entry {
+                source_hash: "0x01".to_owned(),
+                contract: serde_json::json!({}),
+            },
+        );
+        cache.entries.insert(
+            "B.sol:B".to_owned(),
+            Entry {
+                source_hash: "0x02".to_owned(),
+                contract: serde_json::json!({}),
+            },
+        );
+
+        let visited = BTreeSet::from(["A.sol:A".to_owned()]);
+        cache.retain_visited(&visited);
+
+        assert!(cache.entries.contains_key("A.sol:A"));
+        assert!(!cache.entries.contains_key("B.sol:B"));
+    }
+}
@@ -2,12 +2,17 @@
 //! The `solc --standard-json` output representation.
 //!
 
+pub mod artifact;
+pub mod build_info;
+pub mod cache;
 pub mod contract;
 pub mod error;
 pub mod source;
 
 use std::collections::BTreeMap;
 
+use rayon::iter::IntoParallelIterator;
+use rayon::iter::ParallelIterator;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -20,6 +25,11 @@ use crate::solc::pipeline::Pipeline as SolcPipeline;
 use crate::yul::lexer::Lexer;
 use crate::yul::parser::statement::object::Object;
 
+use crate::solc::standard_json::input::Input as StandardJsonInput;
+
+use self::build_info::BuildInfo;
+use self::cache::keccak256;
+use self::cache::Cache;
 use self::contract::Contract;
 use self::error::Error as SolcStandardJsonOutputError;
 use self::source::Source;
@@ -49,21 +59,95 @@ pub struct Output {
     pub zk_version: Option<String>,
 }
 
+///
+/// The front-end input language, selecting how `try_to_project` sources the
+/// contracts it lowers.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// Solidity compiled by `solc`; contracts are taken from the `contracts`
+    /// map and lowered according to the selected `SolcPipeline`.
+    Solidity,
+    /// Hand-written Yul objects supplied directly in the standard-JSON
+    /// `sources`, compiled without a `solc` front-end run.
+    Yul,
+}
+
+///
+/// The hash mode of the metadata appended to produced bytecode.
+///
+/// The selected mode is forwarded to the [`Project`], whose codegen and linking
+/// apply it; this type only selects the mode, it does not itself rewrite
+/// bytecode.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataHash {
+    /// Suppress the trailing CBOR metadata entirely, for byte-stable bytecode.
+    None,
+    /// Append an IPFS multihash of the metadata.
+    IPFS,
+    /// Append a Swarm `bzzr1` multihash of the metadata.
+    Bzzr1,
+}
+
+///
+/// The unlowered source of a single contract, collected sequentially and then
+/// lexed and parsed on the rayon thread pool.
+///
+enum ContractSourceInput {
+    /// The optimized Yul IR, to be lexed and parsed.
+    Yul(String),
+    /// The EVM legacy assembly, already preprocessed in place.
+    EVMLA(Assembly),
+}
+
 impl Output {
     ///
     /// Converts the `solc` JSON output into a convenient project representation.
     ///
+    /// Yul lexing and parsing is fanned out across a rayon pool of `thread_count`
+    /// threads (full CPU utilization when `None`), after the in-place
+    /// `preprocess_dependencies` pass. With `cache_path` set, contracts unchanged
+    /// since a previous run are restored from the on-disk cache. [`Language::Yul`]
+    /// takes a separate, sequential path (see `try_yul_sources_to_project`).
+    ///
     pub fn try_to_project(
         &mut self,
+        input: &StandardJsonInput,
         libraries: BTreeMap<String, BTreeMap<String, String>>,
+        language: Language,
         pipeline: SolcPipeline,
         version: &semver::Version,
+        thread_count: Option<usize>,
+        metadata_hash: MetadataHash,
+        cache_path: Option<&std::path::Path>,
         debug_config: Option<&compiler_llvm_context::DebugConfig>,
     ) -> anyhow::Result<Project> {
+        if let Language::Yul = language {
+            return self.try_yul_sources_to_project(
+                input,
+                libraries,
+                version,
+                metadata_hash,
+                debug_config,
+            );
+        }
+
         if let SolcPipeline::EVMLA = pipeline {
             self.preprocess_dependencies()?;
         }
 
+        let mut cache = match cache_path {
+            Some(path) => Some(Cache::load(
+                path,
+                self.long_version.clone().unwrap_or_default(),
+                self.zk_version.clone().unwrap_or_default(),
+                pipeline,
+                &libraries,
+            )?),
+            None => None,
+        };
+
         let files = match self.contracts.as_mut() {
             Some(files) => files,
             None => {
@@ -76,13 +160,15 @@ impl Output {
                 );
             }
         };
-        let mut project_contracts = BTreeMap::new();
 
+        let mut project_contracts = BTreeMap::new();
+        let mut source_hashes = BTreeMap::new();
+        let mut inputs = Vec::with_capacity(files.values().map(BTreeMap::len).sum());
         for (path, contracts) in files.iter_mut() {
             for (name, contract) in contracts.iter_mut() {
                 let full_path = format!("{path}:{name}");
 
-                let source = match pipeline {
+                let (source_hash, contract_input) = match pipeline {
                     SolcPipeline::Yul => {
                         let ir_optimized = match contract.ir_optimized.take() {
                             Some(ir_optimized) => ir_optimized,
@@ -96,12 +182,8 @@ impl Output {
                             debug_config.dump_yul(full_path.as_str(), ir_optimized.as_str())?;
                         }
 
-                        let mut lexer = Lexer::new(ir_optimized.clone());
-                        let object = Object::parse(&mut lexer, None).map_err(|error| {
-                            anyhow::anyhow!("Contract `{}` parsing error: {:?}", full_path, error)
-                        })?;
-
-                        ProjectContractSource::new_yul(ir_optimized, object)
+                        let source_hash = keccak256(ir_optimized.as_bytes());
+                        (source_hash, ContractSourceInput::Yul(ir_optimized))
                     }
                     SolcPipeline::EVMLA => {
                         let assembly =
@@ -110,27 +192,196 @@ impl Output {
                                 None => continue,
                             };
 
-                        ProjectContractSource::new_evmla(assembly)
+                        let source_hash = assembly.keccak256();
+                        (source_hash, ContractSourceInput::EVMLA(assembly))
                     }
                 };
 
+                if let Some(cached) = cache
+                    .as_ref()
+                    .and_then(|cache| cache.get(full_path.as_str(), source_hash.as_str()))
+                {
+                    let project_contract: ProjectContract =
+                        serde_json::from_value(cached.clone()).map_err(|error| {
+                            anyhow::anyhow!(
+                                "Cached contract `{}` deserialization error: {}",
+                                full_path,
+                                error
+                            )
+                        })?;
+                    project_contracts.insert(full_path, project_contract);
+                    continue;
+                }
+
+                source_hashes.insert(full_path.clone(), source_hash);
+                inputs.push((full_path, contract_input));
+            }
+        }
+
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(thread_count.unwrap_or_default())
+            .build()
+            .map_err(|error| anyhow::anyhow!("Thread pool initialization error: {error}"))?;
+        let lowered: Vec<(String, anyhow::Result<ProjectContractSource>)> = thread_pool.install(|| {
+            inputs
+                .into_par_iter()
+                .map(|(full_path, contract_input)| {
+                    let source = match contract_input {
+                        ContractSourceInput::Yul(ir_optimized) => {
+                            let mut lexer = Lexer::new(ir_optimized.clone());
+                            Object::parse(&mut lexer, None)
+                                .map_err(|error| {
+                                    anyhow::anyhow!(
+                                        "Contract `{}` parsing error: {:?}",
+                                        full_path,
+                                        error
+                                    )
+                                })
+                                .map(|object| ProjectContractSource::new_yul(ir_optimized, object))
+                        }
+                        ContractSourceInput::EVMLA(assembly) => {
+                            Ok(ProjectContractSource::new_evmla(assembly))
+                        }
+                    };
+                    (full_path, source)
+                })
+                .collect()
+        });
+
+        let mut lowered_sources = BTreeMap::new();
+        for (full_path, source) in lowered {
+            lowered_sources.insert(full_path, source?);
+        }
+
+        for (path, contracts) in files.iter_mut() {
+            for (name, contract) in contracts.iter_mut() {
+                let full_path = format!("{path}:{name}");
+                let source = match lowered_sources.remove(full_path.as_str()) {
+                    Some(source) => source,
+                    None => continue,
+                };
+
                 let project_contract =
                     ProjectContract::new(full_path.clone(), source, Some(contract));
+                if let (Some(cache), Some(source_hash)) =
+                    (cache.as_mut(), source_hashes.get(full_path.as_str()))
+                {
+                    cache.insert(full_path.clone(), source_hash.to_owned(), &project_contract)?;
+                }
                 project_contracts.insert(full_path, project_contract);
             }
         }
 
+        if let (Some(cache), Some(path)) = (cache.as_mut(), cache_path) {
+            cache.retain_visited(&project_contracts.keys().cloned().collect());
+            cache.write(path)?;
+        }
+
+        Ok(Project::new(
+            version.to_owned(),
+            project_contracts,
+            libraries,
+            metadata_hash,
+        ))
+    }
+
+    ///
+    /// Lowers raw Yul objects supplied directly in the standard-JSON `input`.
+    ///
+    /// The Yul text is taken from the inline `input` source content, not from
+    /// the `Output` sources (which only carry `ast`/`id`) nor from disk. The
+    /// `full_path` is synthesized from the source path and the object's
+    /// top-level name. No `contracts` map or `solc` pipeline run is required.
+    ///
+    /// This path runs sequentially and is not cached: the `thread_count` and
+    /// `cache_path` of `try_to_project` apply to the Solidity path only.
+    ///
+    fn try_yul_sources_to_project(
+        &mut self,
+        input: &StandardJsonInput,
+        libraries: BTreeMap<String, BTreeMap<String, String>>,
+        version: &semver::Version,
+        metadata_hash: MetadataHash,
+        debug_config: Option<&compiler_llvm_context::DebugConfig>,
+    ) -> anyhow::Result<Project> {
+        let mut project_contracts = BTreeMap::new();
+        for (path, source) in input.sources.iter() {
+            let ir_optimized = source.content.clone();
+            if ir_optimized.is_empty() {
+                continue;
+            }
+
+            if let Some(debug_config) = debug_config {
+                debug_config.dump_yul(path.as_str(), ir_optimized.as_str())?;
+            }
+
+            let mut lexer = Lexer::new(ir_optimized.clone());
+            let object = Object::parse(&mut lexer, None).map_err(|error| {
+                anyhow::anyhow!("Contract `{}` parsing error: {:?}", path, error)
+            })?;
+
+            let full_path = format!("{}:{}", path, object.identifier);
+            let source = ProjectContractSource::new_yul(ir_optimized, object);
+            let project_contract = ProjectContract::new(full_path.clone(), source, None);
+            project_contracts.insert(full_path, project_contract);
+        }
+
         Ok(Project::new(
             version.to_owned(),
             project_contracts,
             libraries,
+            metadata_hash,
         ))
     }
 
+    ///
+    /// Bundles `input` and this output into a reproducible build-info artifact.
+    ///
+    /// The artifact carries a deterministic build id, so two invocations with
+    /// identical inputs produce byte-identical build-info files.
+    ///
+    pub fn to_build_info(&self, input: &StandardJsonInput) -> anyhow::Result<BuildInfo> {
+        BuildInfo::new(input.to_owned(), self.to_owned())
+    }
+
+    ///
+    /// Removes the `ast` request from a standard-JSON `outputSelection`.
+    ///
+    /// The driver calls this before invoking `solc` when AST analysis is
+    /// disabled, so `solc` neither emits nor parses the AST; skipping it cuts
+    /// compile time and peak memory on projects that do not need the zkEVM
+    /// warning pass.
+    ///
+    pub fn disable_ast_output_selection(output_selection: &mut serde_json::Value) {
+        let files = match output_selection.as_object_mut() {
+            Some(files) => files,
+            None => return,
+        };
+        for selection in files.values_mut() {
+            let per_contract = match selection.as_object_mut() {
+                Some(per_contract) => per_contract,
+                None => continue,
+            };
+            for requested in per_contract.values_mut() {
+                if let Some(list) = requested.as_array_mut() {
+                    list.retain(|item| item.as_str() != Some("ast"));
+                }
+            }
+        }
+    }
+
     ///
     /// Traverses the AST and returns the list of additional errors and warnings.
     ///
-    pub fn preprocess_ast(&mut self) -> anyhow::Result<()> {
+    /// A no-op when `enabled` is `false`; pair it with
+    /// [`Self::disable_ast_output_selection`] to also keep `solc` from emitting
+    /// the AST in the first place.
+    ///
+    pub fn preprocess_ast(&mut self, enabled: bool) -> anyhow::Result<()> {
+        if !enabled {
+            return Ok(());
+        }
+
         let sources = match self.sources.as_ref() {
             Some(sources) => sources,
             None => return Ok(()),
@@ -240,4 +491,230 @@ impl Output {
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Language;
+    use super::MetadataHash;
+    use super::Output;
+    use crate::solc::pipeline::Pipeline as SolcPipeline;
+    use crate::solc::standard_json::input::Input as StandardJsonInput;
+
+    fn input() -> StandardJsonInput {
+        serde_json::from_value(serde_json::json!({
+            "language": "Solidity",
+            "sources": {},
+            "settings": {},
+        }))
+        .expect("standard-json input")
+    }
+
+    fn valid_yul_object(name: &str) -> String {
+        format!("object \"{name}\" {{ code {{ }} }}")
+    }
+
+    fn output_with_yul_contracts(entries: &[(&str, &str, &str)]) -> Output {
+        let mut contracts = serde_json::Map::new();
+        for (path, name, ir_optimized) in entries {
+            contracts
+                .entry((*path).to_owned())
+                .or_insert_with(|| serde_json::json!({}))
+                .as_object_mut()
+                .expect("file entry")
+                .insert((*name).to_owned(), serde_json::json!({ "irOptimized": ir_optimized }));
+        }
+        serde_json::from_value(serde_json::json!({
+            "contracts": contracts,
+            "version": "0.8.24",
+            "long_version": "0.8.24+commit.abcdef",
+        }))
+        .expect("output")
+    }
+
+    #[test]
+    fn parse_error_message_is_stable_across_thread_counts() {
+        let valid_a = valid_yul_object("A");
+        let valid_c = valid_yul_object("C");
+
+        for thread_count in [Some(1), Some(4)] {
+            let mut output = output_with_yul_contracts(&[
+                ("A.sol", "A", valid_a.as_str()),
+                ("B.sol", "B", "this is not valid yul"),
+                ("C.sol", "C", valid_c.as_str()),
+            ]);
+
+            let error = output
+                .try_to_project(
+                    &input(),
+                    BTreeMap::new(),
+                    Language::Solidity,
+                    SolcPipeline::Yul,
+                    &semver::Version::new(0, 8, 24),
+                    thread_count,
+                    MetadataHash::None,
+                    None,
+                    None,
+                )
+                .expect_err("invalid Yul source must fail to parse");
+
+            assert!(
+                error.to_string().contains("Contract `B.sol:B` parsing error"),
+                "unexpected error for thread_count {:?}: {}",
+                thread_count,
+                error
+            );
+        }
+    }
+
+    fn input_with_yul_sources(entries: &[(&str, &str)]) -> StandardJsonInput {
+        let mut sources = serde_json::Map::new();
+        for (path, content) in entries {
+            sources.insert((*path).to_owned(), serde_json::json!({ "content": content }));
+        }
+        serde_json::from_value(serde_json::json!({
+            "language": "Yul",
+            "sources": sources,
+            "settings": {},
+        }))
+        .expect("standard-json input")
+    }
+
+    fn empty_output() -> Output {
+        Output {
+            contracts: None,
+            sources: None,
+            errors: None,
+            version: Some("0.8.24".to_owned()),
+            long_version: Some("0.8.24+commit.abcdef".to_owned()),
+            zk_version: Some("1.5.0".to_owned()),
+        }
+    }
+
+    #[test]
+    fn yul_language_synthesizes_full_path_and_skips_empty_sources() {
+        let valid_a = valid_yul_object("A");
+        let input = input_with_yul_sources(&[("A.yul", valid_a.as_str()), ("Empty.yul", "")]);
+        let mut output = empty_output();
+
+        let project = output
+            .try_yul_sources_to_project(
+                &input,
+                BTreeMap::new(),
+                &semver::Version::new(0, 8, 24),
+                MetadataHash::None,
+                None,
+            )
+            .expect("raw Yul sources must lower without a `contracts` map or `solc` pipeline");
+
+        assert_eq!(project.contracts.len(), 1);
+        assert!(project.contracts.contains_key("A.yul:A"));
+    }
+
+    #[test]
+    fn yul_language_path_is_reachable_through_try_to_project() {
+        let valid_a = valid_yul_object("A");
+        let input = input_with_yul_sources(&[("A.yul", valid_a.as_str())]);
+        let mut output = empty_output();
+
+        let project = output
+            .try_to_project(
+                &input,
+                BTreeMap::new(),
+                Language::Yul,
+                SolcPipeline::Yul,
+                &semver::Version::new(0, 8, 24),
+                None,
+                MetadataHash::None,
+                None,
+                None,
+            )
+            .expect("Language::Yul must dispatch to the raw Yul path");
+
+        assert!(project.contracts.contains_key("A.yul:A"));
+    }
+
+    #[test]
+    fn disable_ast_output_selection_strips_ast_key() {
+        let mut selection = serde_json::json!({
+            "*": {
+                "": ["ast"],
+                "A": ["abi", "ast", "evm.bytecode"],
+            }
+        });
+
+        Output::disable_ast_output_selection(&mut selection);
+
+        assert_eq!(selection["*"][""], serde_json::json!([]));
+        assert_eq!(selection["*"]["A"], serde_json::json!(["abi", "evm.bytecode"]));
+    }
+
+    fn output_with_ast(path: &str) -> Output {
+        serde_json::from_value(serde_json::json!({
+            "sources": {
+                path: { "ast": { "nodes": [] } },
+            },
+        }))
+        .expect("output with ast")
+    }
+
+    #[test]
+    fn preprocess_ast_disabled_is_a_no_op() {
+        let mut output = output_with_ast("A.sol");
+        assert!(output.errors.is_none());
+
+        output
+            .preprocess_ast(false)
+            .expect("disabled AST pass must be a no-op");
+
+        assert!(output.errors.is_none());
+    }
+
+    #[test]
+    fn metadata_hash_threads_through_yul_sources_path() {
+        let valid_a = valid_yul_object("A");
+        let input = input_with_yul_sources(&[("A.yul", valid_a.as_str())]);
+
+        for metadata_hash in [MetadataHash::None, MetadataHash::IPFS, MetadataHash::Bzzr1] {
+            let mut output = empty_output();
+            let project = output
+                .try_yul_sources_to_project(
+                    &input,
+                    BTreeMap::new(),
+                    &semver::Version::new(0, 8, 24),
+                    metadata_hash,
+                    None,
+                )
+                .expect("raw Yul sources must lower");
+
+            assert_eq!(project.metadata_hash, metadata_hash);
+        }
+    }
+
+    #[test]
+    fn metadata_hash_threads_through_solidity_path() {
+        let valid_a = valid_yul_object("A");
+        let contract_output = output_with_yul_contracts(&[("A.sol", "A", valid_a.as_str())]);
+
+        for metadata_hash in [MetadataHash::None, MetadataHash::IPFS, MetadataHash::Bzzr1] {
+            let mut output = contract_output.clone();
+            let project = output
+                .try_to_project(
+                    &input(),
+                    BTreeMap::new(),
+                    Language::Solidity,
+                    SolcPipeline::Yul,
+                    &semver::Version::new(0, 8, 24),
+                    Some(1),
+                    metadata_hash,
+                    None,
+                    None,
+                )
+                .expect("valid Yul sources must lower");
+
+            assert_eq!(project.metadata_hash, metadata_hash);
+        }
+    }
 }
\ No newline at end of file